@@ -1,36 +1,231 @@
 use std::fmt::Debug;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, BufWriter};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use rbx_dom_weak::WeakDom;
+use rbx_dom_weak::{InstanceBuilder, WeakDom};
+
+/// The serialization format a DOM should be written back out as. Each variant corresponds to one
+/// of the four Roblox file extensions, and is derived from an output path by `detect_output_kind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputKind {
+    /// Roblox Place file saved in binary format.
+    Rbxl,
+    /// Roblox Place file saved in xml format.
+    Rbxlx,
+    /// Roblox Model file saved in binary format.
+    Rbxm,
+    /// Roblox Model file saved in xml format.
+    Rbxmx,
+}
 
 fn main() {
-    println!("--------------------------------------------------");
-    println!("Hello! Please input your Roblox Place/Model file path.");
-    println!("--------------------------------------------------");
+    // parse the command line arguments so the tool can be driven non-interactively. If no
+    // arguments are given we fall back to the interactive prompt below.
+    let arguments: Arguments = match parse_arguments() {
+        Ok(arguments) => arguments,
+        Err(why) => panic!("{}", why),
+    };
 
-    let file_path: PathBuf = get_file_path_from_terminal();
+    let file_path: PathBuf = match arguments.input.clone() {
+        Some(input) => input,
+        None => {
+            println!("--------------------------------------------------");
+            println!("Hello! Please input your Roblox Place/Model file path.");
+            println!("--------------------------------------------------");
+            get_file_path_from_terminal()
+        },
+    };
+
+    // if requested, dump rbx_binary's chunk-level textual debug format for binary inputs and
+    // stop, rather than constructing a DOM.
+    if arguments.text_format {
+        println!("--------------------------------------------------");
+        println!("Decoding binary text format...");
+        println!("--------------------------------------------------");
+
+        match print_text_format(&file_path) {
+            Ok(_) => return,
+            Err(why) => panic!("{}", why),
+        };
+    }
 
     println!("--------------------------------------------------");
     println!("Constructing DOM...");
     println!("--------------------------------------------------");
 
-    let dom: WeakDom = match get_dom_from_extension(file_path) {
-        Ok(result) => result,
-        Err(why) => panic!("{}", why),
+    // a directory input is assembled from its unpacked source files, while a single packed file
+    // is decoded by extension.
+    let mut dom: WeakDom = if file_path.is_dir() {
+        match assemble_dom_from_directory(&file_path) {
+            Ok(result) => result,
+            Err(why) => panic!("{}", why),
+        }
+    } else {
+        match get_dom_from_extension(file_path) {
+            Ok(result) => result,
+            Err(why) => panic!("{}", why),
+        }
     };
 
+    // if an output path was given, re-serialize the DOM to it instead of printing.
+    if let Some(output) = arguments.output.clone() {
+        println!("--------------------------------------------------");
+        println!("Writing DOM to {}...", output.display());
+        println!("--------------------------------------------------");
+
+        // drop the branches the filters remove so the written file mirrors the printed tree,
+        // keeping containers of a match so the serialized hierarchy stays intact.
+        let root_ref = dom.root_ref();
+        prune_to_filters(&mut dom, root_ref, &arguments.filters);
+
+        match write_dom_to_extension(&dom, &output) {
+            Ok(_) => println!("DOM successfully written."),
+            Err(why) => panic!("{}", why),
+        };
+
+        return;
+    }
+
     println!("--------------------------------------------------");
     println!("DOM successfully constructed.");
     println!("Root instances in file:");
     println!("--------------------------------------------------");
-    
+
     for &referent in dom.root().children() {
-        let instance = dom.get_by_ref(referent).unwrap();
-        println!("- {}", instance.name);
+        print_instance_tree(&dom, referent, 0, &arguments);
+    }
+}
+
+/// Recursively prints an instance and its descendants as an indented `Name (ClassName)` tree.
+/// Branches that contain no match are pruned entirely, and containers of a match are kept so the
+/// printed tree mirrors what `prune_to_filters` leaves behind on the write path (the depth limit
+/// below applies to printing only; the write path keeps every depth). This lets
+/// the indentation track the real recursion depth, since every ancestor of a printed instance is
+/// itself printed. When `arguments.properties` is set every entry in the instance's `properties`
+/// map is printed beneath it with its Variant value. Traversal stops once `arguments.depth` (when
+/// given) is reached so large places stay readable.
+fn print_instance_tree(dom: &WeakDom, referent: rbx_dom_weak::types::Ref, depth: usize, arguments: &Arguments) {
+    // stop descending once we have walked the requested number of levels.
+    if let Some(max_depth) = arguments.depth {
+        if depth > max_depth {
+            return;
+        }
+    }
+
+    // drop branches the filters remove outright, keeping containers of a match so the hierarchy
+    // stays intact — the same rule the write path applies in `prune_to_filters`.
+    if !subtree_matches(dom, referent, &arguments.filters) {
+        return;
+    }
+
+    let instance = dom.get_by_ref(referent).unwrap();
+
+    let indent = "  ".repeat(depth);
+    println!("{}- {} ({})", indent, instance.name, instance.class);
+
+    if arguments.properties {
+        for (property_name, property_value) in &instance.properties {
+            println!("{}    {} = {:?}", indent, property_name, property_value);
+        }
     }
+
+    for &child in instance.children() {
+        print_instance_tree(dom, child, depth + 1, arguments);
+    }
+}
+
+/// The parsed command line arguments. `input`/`output` are left as `None` when their flags are
+/// absent, and `filters` is empty when no `-f/--filter` flags are given (matching everything).
+struct Arguments {
+    input: Option<PathBuf>,
+    output: Option<PathBuf>,
+    filters: Filters,
+    depth: Option<usize>,
+    properties: bool,
+    text_format: bool,
+}
+
+/// Include/exclude patterns for instances, borrowing the filter model from windows-bindgen's
+/// bindgen CLI. A bare pattern is an include and a `!`-prefixed pattern is an exclude; each is
+/// matched against an instance's ClassName or Name.
+struct Filters {
+    includes: Vec<String>,
+    excludes: Vec<String>,
+}
+
+impl Filters {
+    /// Creates an empty filter set, which matches every instance.
+    fn new() -> Filters {
+        Filters { includes: Vec::new(), excludes: Vec::new() }
+    }
+
+    /// Adds a single `-f/--filter` pattern, routing `!`-prefixed patterns to the exclude list and
+    /// everything else to the include list.
+    fn push(&mut self, pattern: &str) {
+        match pattern.strip_prefix('!') {
+            Some(excluded) => self.excludes.push(String::from(excluded)),
+            None => self.includes.push(String::from(pattern)),
+        };
+    }
+
+    /// Returns whether an instance with the given ClassName and Name passes the filters. An
+    /// instance is kept when it is not excluded and, if any includes are present, matches one of
+    /// them. With no includes every non-excluded instance is kept.
+    fn matches(&self, class_name: &str, name: &str) -> bool {
+        if self.excludes.iter().any(|pattern| pattern == class_name || pattern == name) {
+            return false;
+        }
+
+        if self.includes.is_empty() {
+            return true;
+        }
+
+        return self.includes.iter().any(|pattern| pattern == class_name || pattern == name);
+    }
+}
+
+/// Parses `std::env::args` into an Arguments struct, accepting `-i/--in <path>`, `-o/--out <path>`,
+/// and repeatable `-f/--filter <pattern>`. Returns the parsed Arguments if successful, or a String
+/// with a reason why the error occurred in the case of an error.
+fn parse_arguments() -> Result<Arguments, String> {
+    let mut input: Option<PathBuf> = None;
+    let mut output: Option<PathBuf> = None;
+    let mut filters: Filters = Filters::new();
+    let mut depth: Option<usize> = None;
+    let mut properties: bool = false;
+    let mut text_format: bool = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-i" | "--in" => match args.next() {
+                Some(value) => input = Some(PathBuf::from(value)),
+                None => return Err(String::from("Expected a path after -i/--in.")),
+            },
+            "-o" | "--out" => match args.next() {
+                Some(value) => output = Some(PathBuf::from(value)),
+                None => return Err(String::from("Expected a path after -o/--out.")),
+            },
+            "-f" | "--filter" => match args.next() {
+                Some(value) => filters.push(&value),
+                None => return Err(String::from("Expected a pattern after -f/--filter.")),
+            },
+            "-d" | "--depth" => match args.next() {
+                Some(value) => match value.parse::<usize>() {
+                    Ok(parsed) => depth = Some(parsed),
+                    Err(_) => return Err(String::from("Expected a number after -d/--depth.")),
+                },
+                None => return Err(String::from("Expected a number after -d/--depth.")),
+            },
+            "-p" | "--properties" => properties = true,
+            "-t" | "--text-format" => text_format = true,
+            _ => return Err(String::from(format!("Unrecognized argument: {}", arg))),
+        };
+    }
+
+    return Ok(Arguments { input, output, filters, depth, properties, text_format });
 }
 
 /// Reads stdin until a newline character and parses the input into the given type.
@@ -61,7 +256,8 @@ where <T as FromStr>::Err: Debug {
 
 /// Prompts the terminal to accept a valid file path from the user. Will loop if any errors occur.
 /// Checks if the file at the given path exists, and ensures that the file at the given path is
-/// saved in .rbxl, .rbxlx, .rbxm, or .rbxmx format before returning a PathBuf.
+/// saved in .rbxl, .rbxlx, .rbxm, or .rbxmx format before returning a PathBuf. A directory is also
+/// accepted and is later assembled from its unpacked source files.
 fn get_file_path_from_terminal() -> PathBuf {
     loop {
         println!();
@@ -98,6 +294,16 @@ fn get_file_path_from_terminal() -> PathBuf {
         }
 
         let display = input_file_path.display();
+
+        // a directory is assembled from its unpacked source files rather than decoded, so accept
+        // it before we reach the extension checks below.
+        if input_file_path.is_dir() {
+            println!("--------------------------------------------------");
+            println!("You entered: \n{} \n(This is a directory of source files)", display);
+            println!("--------------------------------------------------");
+            return input_file_path.to_path_buf();
+        }
+
         let extension = input_file_path.extension().unwrap().to_str().unwrap();
 
         println!("--------------------------------------------------");
@@ -183,6 +389,253 @@ fn get_dom_from_extension(path_buffer: PathBuf) -> Result<WeakDom, String> {
     };
 }
 
+/// Assembles a directory of unpacked source files into a single WeakDom. A synthesized Folder
+/// named after the directory becomes the sole root instance, and the directory tree is walked into
+/// it so subfolders become Folder instances and files become the instances they describe. Returns
+/// the assembled WeakDom if successful, or a String with a reason why the error occurred in the
+/// case of an error.
+fn assemble_dom_from_directory(directory: &PathBuf) -> Result<WeakDom, String> {
+    let root_name = match directory.file_name() {
+        Some(name) => name.to_string_lossy().into_owned(),
+        None => String::from("Project"),
+    };
+
+    let mut dom = WeakDom::new(InstanceBuilder::new("DataModel"));
+    let root_ref = dom.root_ref();
+    let folder_ref = dom.insert(root_ref, InstanceBuilder::new("Folder").with_name(root_name));
+
+    build_directory_into(&mut dom, folder_ref, directory)?;
+
+    return Ok(dom);
+}
+
+/// Recursively grafts the contents of a directory under the given parent instance. Subdirectories
+/// become child Folder instances, `.rbxm`/`.rbxmx` models have their root instances transferred in
+/// place, and `.lua`/`.luau` scripts become Script/LocalScript/ModuleScript instances. Other files
+/// are ignored. Returns Ok(()) on success, or a String with a reason why the error occurred in the
+/// case of an error.
+fn build_directory_into(dom: &mut WeakDom, parent: rbx_dom_weak::types::Ref, directory: &PathBuf) -> Result<(), String> {
+    let display = directory.display();
+    let error_message = String::from(format!("An error occurred while reading the source directory: {}", display));
+
+    let read_dir = match std::fs::read_dir(directory) {
+        Ok(read_dir) => read_dir,
+        Err(_) => return Err(error_message),
+    };
+
+    // `read_dir` yields entries in filesystem order, so collect and sort them by file name to keep
+    // the assembled DOM — and any file built from it — reproducible across machines and runs.
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for entry in read_dir {
+        match entry {
+            Ok(entry) => paths.push(entry.path()),
+            Err(_) => return Err(error_message),
+        };
+    }
+    paths.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+
+    for path in paths {
+        if path.is_dir() {
+            // a subfolder mirrors into a Folder instance that its own contents hang beneath.
+            let name = match path.file_name() {
+                Some(name) => name.to_string_lossy().into_owned(),
+                None => continue,
+            };
+            let child_folder = dom.insert(parent, InstanceBuilder::new("Folder").with_name(name));
+            build_directory_into(dom, child_folder, &path)?;
+            continue;
+        }
+
+        let extension: &str = match path.extension() {
+            Some(extension) => match extension.to_str() {
+                Some(extension) => extension,
+                None => continue,
+            },
+            None => continue,
+        };
+
+        match extension {
+            // a packed model's root instances are transferred into the assembled DOM directly.
+            "rbxm" | "rbxmx" => {
+                let mut sub_dom = get_dom_from_extension(path.clone())?;
+                let children: Vec<rbx_dom_weak::types::Ref> = sub_dom.root().children().to_vec();
+                for child in children {
+                    sub_dom.transfer(child, dom, parent);
+                }
+            },
+            // a script file becomes the script instance its filename suffix describes.
+            "lua" | "luau" => {
+                let source = match std::fs::read_to_string(&path) {
+                    Ok(source) => source,
+                    Err(_) => return Err(error_message),
+                };
+                let (class_name, name) = script_class_and_name(&path);
+                dom.insert(
+                    parent,
+                    InstanceBuilder::new(class_name)
+                        .with_name(name)
+                        .with_property("Source", source),
+                );
+            },
+            // anything else is not part of a Roblox project and is skipped.
+            _ => continue,
+        };
+    }
+
+    return Ok(());
+}
+
+/// Derives the script ClassName and instance Name from a script file's path, following the
+/// familiar `.server`/`.client` suffix convention. `*.server.lua(u)` becomes a Script,
+/// `*.client.lua(u)` a LocalScript, and everything else a ModuleScript; the suffixes are stripped
+/// from the returned name.
+fn script_class_and_name(path: &Path) -> (&'static str, String) {
+    let stem = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+
+    // trim the extension first so the suffix check only sees the remaining stem.
+    let stem = stem
+        .strip_suffix(".luau")
+        .or_else(|| stem.strip_suffix(".lua"))
+        .unwrap_or(stem);
+
+    if let Some(name) = stem.strip_suffix(".server") {
+        return ("Script", String::from(name));
+    }
+
+    if let Some(name) = stem.strip_suffix(".client") {
+        return ("LocalScript", String::from(name));
+    }
+
+    return ("ModuleScript", String::from(stem));
+}
+
+/// Reads the file extension of the given path and returns the matching OutputKind. Returns the
+/// OutputKind corresponding to the extension if it names a valid Roblox file, or a String with a
+/// reason why the error occurred in the case of an error.
+fn detect_output_kind(path_buffer: &PathBuf) -> Result<OutputKind, String> {
+    let display = path_buffer.display();
+    let error_message = String::from(format!("That isn't a valid output path: {} \nPlease ensure the output file is saved in rbxl, rbxlx, rbxm, or rbxmx format.", display));
+
+    let extension: &str = match path_buffer.extension() {
+        Some(extension) => match extension.to_str() {
+            Some(extension) => extension,
+            None => return Err(error_message),
+        },
+        None => return Err(error_message),
+    };
+
+    // map the output extension onto the format the DOM should be re-serialized as.
+    match extension {
+        // Roblox Place file saved in binary format.
+        "rbxl" => return Ok(OutputKind::Rbxl),
+        // Roblox Place file saved in xml format.
+        "rbxlx" => return Ok(OutputKind::Rbxlx),
+        // Roblox Model file saved in binary format.
+        "rbxm" => return Ok(OutputKind::Rbxm),
+        // Roblox Model file saved in xml format.
+        "rbxmx" => return Ok(OutputKind::Rbxmx),
+        _ => return Err(error_message),
+    };
+}
+
+/// Re-serializes the given WeakDom to the output path, choosing the encoder from the output
+/// extension. Binary targets go through `rbx_binary::to_writer` and xml targets through
+/// `rbx_xml::to_writer`, both serializing the root's children. Returns Ok(()) on success, or a
+/// String with a reason why the error occurred in the case of an error.
+fn write_dom_to_extension(dom: &WeakDom, path_buffer: &PathBuf) -> Result<(), String> {
+    let output_kind = detect_output_kind(path_buffer)?;
+    let display = path_buffer.display();
+    let error_message = String::from(format!("An error occurred while writing a DOM to the given file path: {}", display));
+
+    let writer = BufWriter::new(match File::create(path_buffer) {
+        Ok(file) => file,
+        Err(_) => return Err(error_message),
+    });
+
+    let children = dom.root().children();
+
+    // dispatch to the encoder matching the requested output format.
+    match output_kind {
+        OutputKind::Rbxl | OutputKind::Rbxm => {
+            match rbx_binary::to_writer(writer, dom, children) {
+                Ok(_) => return Ok(()),
+                Err(_) => return Err(error_message),
+            };
+        },
+        OutputKind::Rbxlx | OutputKind::Rbxmx => {
+            match rbx_xml::to_writer(writer, dom, children, rbx_xml::EncodeOptions::new()) {
+                Ok(_) => return Ok(()),
+                Err(_) => return Err(error_message),
+            };
+        },
+    };
+}
+
+/// Recursively removes every subtree beneath `parent` that contains no instance matching the
+/// filters, so the surviving DOM holds only the instances the filters would have printed. An empty
+/// filter set matches everything and leaves the DOM untouched.
+fn prune_to_filters(dom: &mut WeakDom, parent: rbx_dom_weak::types::Ref, filters: &Filters) {
+    let children: Vec<rbx_dom_weak::types::Ref> = dom.get_by_ref(parent).unwrap().children().to_vec();
+
+    for child in children {
+        if subtree_matches(dom, child, filters) {
+            // keep this branch but still prune the non-matching twigs inside it.
+            prune_to_filters(dom, child, filters);
+        } else {
+            dom.destroy(child);
+        }
+    }
+}
+
+/// Returns whether an instance or any of its descendants passes the filters, used to decide which
+/// branches `prune_to_filters` may discard.
+fn subtree_matches(dom: &WeakDom, referent: rbx_dom_weak::types::Ref, filters: &Filters) -> bool {
+    let instance = dom.get_by_ref(referent).unwrap();
+
+    if filters.matches(&instance.class, &instance.name) {
+        return true;
+    }
+
+    return instance.children().iter().any(|&child| subtree_matches(dom, child, filters));
+}
+
+/// Renders rbx_binary's unstable chunk-level textual debug format for a binary `.rbxl`/`.rbxm`
+/// file and prints it to stdout. Relies on rbx_binary's `unstable_text_format` feature, which
+/// exposes the `text_format` module. Returns Ok(()) on success, or a String with a reason why the
+/// error occurred in the case of an error.
+fn print_text_format(file_path: &PathBuf) -> Result<(), String> {
+    let display = file_path.display();
+    let error_message = String::from(format!("An error occurred while decoding the binary text format from the given file path: {}", display));
+
+    let extension: &str = match file_path.extension() {
+        Some(extension) => match extension.to_str() {
+            Some(extension) => extension,
+            None => return Err(error_message),
+        },
+        None => return Err(error_message),
+    };
+
+    // the text format only describes binary chunks, so xml place/model files have nothing to dump.
+    match extension {
+        "rbxl" | "rbxm" => {},
+        _ => return Err(String::from(format!("The binary text format is only available for .rbxl/.rbxm files: {}", display))),
+    };
+
+    let buffer = BufReader::new(match File::open(file_path) {
+        Ok(file) => file,
+        Err(_) => return Err(error_message),
+    });
+
+    // `DecodedModel` derives Debug, so its pretty-printed form is the chunk-level dump.
+    let decoded = rbx_binary::text_format::DecodedModel::from_reader(buffer);
+    println!("{:#?}", decoded);
+
+    return Ok(());
+}
+
 /// Reads the file in binary format at the end of the PathBuf. Returns a WeakDom representation of
 /// the file at the end of the PathBuf if successful, or a DecodeError in the case of an error.
 fn get_dom_from_binary(file_path: &PathBuf) -> Result<WeakDom, rbx_binary::DecodeError> {
@@ -193,8 +646,8 @@ fn get_dom_from_binary(file_path: &PathBuf) -> Result<WeakDom, rbx_binary::Decod
 
 /// Reads the file in xml format at the end of the PathBuf. Returns a WeakDom representation of
 /// the file at the end of the PathBuf if successful, or a DecodeError in the case of an error.
-fn get_dom_from_xml(file_path: &PathBuf) -> Result<WeakDom, rbx_binary::DecodeError> {
+fn get_dom_from_xml(file_path: &PathBuf) -> Result<WeakDom, rbx_xml::DecodeError> {
     let buffer = BufReader::new(File::open(file_path).unwrap());
-    let result = rbx_binary::from_reader(buffer);
+    let result = rbx_xml::from_reader(buffer, rbx_xml::DecodeOptions::new());
     return result;
 }
\ No newline at end of file